@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+/// Errors produced while parsing Horizons ephemeris output.
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected a line starting with {expected:?}, found {found:?}")]
+    UnexpectedLabel { expected: String, found: String },
+    #[error("failed to parse {value:?} as a float")]
+    FloatParse { value: String },
+    #[error("input ended before the current record was complete")]
+    UnexpectedEof,
+}
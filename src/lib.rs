@@ -1,5 +1,9 @@
 //! Interact with NASA JPL Horizon system.
 mod client;
+mod error;
 mod parsing;
+mod sp3;
 
-pub use client::{ephemeris, major_bodies};
\ No newline at end of file
+pub use client::{ephemeris, major_bodies};
+pub use error::ParseError;
+pub use sp3::to_sp3;
\ No newline at end of file
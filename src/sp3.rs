@@ -0,0 +1,171 @@
+//! Export ephemeris series to the IGS SP3 precise-orbit text format.
+use std::fmt::Write as _;
+
+use crate::ephemeris::EphemerisVectorItem;
+
+const SECONDS_PER_WEEK: f64 = 604_800.0;
+
+/// Render `items` (samples of a single body, in chronological order, with a
+/// constant sampling interval) as an IGS SP3 (c) precise-orbit text file.
+///
+/// Horizons targets don't carry a standard SP3 satellite id, so callers
+/// supply whatever `body_id` their downstream tooling expects (e.g. `L39`
+/// for a generic body); like real SP3 vehicle ids, it must be at most 3
+/// characters, since the `P`/`V` record columns are fixed-width. Positions
+/// and velocities are written in km and km/s, per the values already stored
+/// on [`EphemerisVectorItem`]. Since every item carries a velocity, the file
+/// is always declared `V` (position and velocity) in the `#c` header.
+///
+/// # Panics
+///
+/// Panics if `body_id` is longer than 3 characters.
+pub fn to_sp3(items: &[EphemerisVectorItem], body_id: &str) -> String {
+    assert!(
+        body_id.chars().count() <= 3,
+        "SP3 satellite ids are at most 3 characters, got {body_id:?}"
+    );
+
+    let mut out = String::new();
+
+    let interval_seconds = match items {
+        [first, second, ..] => (second.epoch - first.epoch).to_seconds(),
+        _ => 0.0,
+    };
+
+    let start_epoch = items.first().map(|item| item.epoch);
+    let (year, month, day, hour, minute, second, nanos) = start_epoch
+        .map(|epoch| epoch.to_gregorian_utc())
+        .unwrap_or((1970, 1, 1, 0, 0, 0, 0));
+
+    let gpst_seconds = start_epoch.map(|epoch| epoch.to_gpst_seconds()).unwrap_or(0.0);
+    let gps_week = (gpst_seconds / SECONDS_PER_WEEK).floor();
+    let seconds_of_week = gpst_seconds - gps_week * SECONDS_PER_WEEK;
+
+    let mjd = start_epoch.map(|epoch| epoch.to_mjd_utc_days()).unwrap_or(0.0);
+    let mjd_int = mjd.floor();
+    let mjd_frac = mjd - mjd_int;
+
+    // #c: data used (P = position only, V = position and velocity).
+    writeln!(
+        out,
+        "#cV{:4} {:2} {:2} {:2} {:2} {:11.8} {:7} ORBIT IGS14 HLM  rhorizons",
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second as f64 + nanos as f64 * 1e-9,
+        items.len(),
+    )
+    .unwrap();
+    // ##: GPS week, seconds of week, epoch interval, and MJD of the first epoch.
+    writeln!(
+        out,
+        "## {:4} {:15.8} {:14.8} {:5} {:15.13}",
+        gps_week, seconds_of_week, interval_seconds, mjd_int, mjd_frac
+    )
+    .unwrap();
+    // %c / %i: data type and count descriptors. rhorizons only ever writes a
+    // single body per file.
+    writeln!(out, "%c cc {:>3} cc ccc ccc ccc ccc ccccc ccccc ccccc ccccc", body_id).unwrap();
+    writeln!(out, "%i    0    0    0    0      0      0      0      0         0").unwrap();
+
+    for item in items {
+        let (year, month, day, hour, minute, second, nanos) = item.epoch.to_gregorian_utc();
+        writeln!(
+            out,
+            "*  {:4} {:2} {:2} {:2} {:2} {:11.8}",
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second as f64 + nanos as f64 * 1e-9,
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "P{:>3} {:13.6} {:13.6} {:13.6}",
+            body_id, item.position[0], item.position[1], item.position[2]
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "V{:>3} {:13.6} {:13.6} {:13.6}",
+            body_id, item.velocity[0], item.velocity[1], item.velocity[2]
+        )
+        .unwrap();
+    }
+
+    out.push_str("EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hifitime::Epoch;
+
+    #[test]
+    fn test_to_sp3_contains_header_and_records() {
+        let items = vec![
+            EphemerisVectorItem {
+                epoch: Epoch::from_jde_tdb(2451545.0),
+                position: [1.0, 2.0, 3.0],
+                velocity: [4.0, 5.0, 6.0],
+            },
+            EphemerisVectorItem {
+                epoch: Epoch::from_jde_tdb(2451545.5),
+                position: [7.0, 8.0, 9.0],
+                velocity: [10.0, 11.0, 12.0],
+            },
+        ];
+
+        let sp3 = to_sp3(&items, "L39");
+
+        assert!(sp3.starts_with("#cV"));
+        assert!(sp3.contains("%c cc L39"));
+        assert!(sp3.contains("PL39"));
+        assert!(sp3.contains("VL39"));
+        assert!(sp3.ends_with("EOF\n"));
+        assert_eq!(2, sp3.matches('*').count());
+    }
+
+    #[test]
+    fn test_to_sp3_header_reflects_epoch_count_and_interval() {
+        let items = vec![
+            EphemerisVectorItem {
+                epoch: Epoch::from_jde_tdb(2451545.0),
+                position: [1.0, 2.0, 3.0],
+                velocity: [4.0, 5.0, 6.0],
+            },
+            EphemerisVectorItem {
+                epoch: Epoch::from_jde_tdb(2451545.5),
+                position: [7.0, 8.0, 9.0],
+                velocity: [10.0, 11.0, 12.0],
+            },
+        ];
+
+        let sp3 = to_sp3(&items, "L39");
+        let header_line = sp3.lines().next().unwrap();
+
+        assert!(header_line.ends_with("      2 ORBIT IGS14 HLM  rhorizons"));
+
+        // The `##` line carries the epoch interval: half a day apart.
+        let interval_line = sp3.lines().nth(1).unwrap();
+        let interval: f64 = interval_line.split_whitespace().nth(3).unwrap().parse().unwrap();
+        assert!((interval - 43200.0).abs() < 1e-3);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 3 characters")]
+    fn test_to_sp3_rejects_body_id_longer_than_three_chars() {
+        let items = vec![EphemerisVectorItem {
+            epoch: Epoch::from_jde_tdb(2451545.0),
+            position: [1.0, 2.0, 3.0],
+            velocity: [4.0, 5.0, 6.0],
+        }];
+
+        to_sp3(&items, "TOOLONG");
+    }
+}